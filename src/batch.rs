@@ -0,0 +1,139 @@
+// 并行批处理模式：对多个输入文件并发地调用插件逻辑，并渲染一个聚合进度条
+use crate::core::Plugin;
+use crate::output::RunContext;
+use clap::ArgMatches;
+use indicatif::{ProgressBar, ProgressStyle};
+use rayon::prelude::*;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+// 将用户传入的 --inputs 值展开为具体文件路径：每一项若能匹配到 glob 结果就展开，
+// 否则按普通文件路径原样使用
+pub fn expand_inputs(patterns: &[String]) -> anyhow::Result<Vec<PathBuf>> {
+    let mut paths = Vec::new();
+
+    for pattern in patterns {
+        let mut matched_any = false;
+        for entry in glob::glob(pattern)? {
+            paths.push(entry?);
+            matched_any = true;
+        }
+        if !matched_any {
+            paths.push(PathBuf::from(pattern));
+        }
+    }
+
+    Ok(paths)
+}
+
+// 对 paths 中的每个文件执行插件逻辑：若插件实现了 run_one 则用 rayon 线程池并发执行，
+// 否则只整体调用一次 run，且 paths 多于一个文件时直接返回错误
+pub fn run_batch(
+    plugin: &Arc<dyn Plugin>,
+    matches: &ArgMatches,
+    ctx: &RunContext,
+    paths: Vec<PathBuf>,
+    jobs: usize,
+) -> anyhow::Result<()> {
+    let progress = ProgressBar::new(paths.len() as u64);
+    progress.set_style(
+        ProgressStyle::with_template("[{elapsed_precise}] {bar:40.cyan/blue} {pos}/{len} 文件已完成")
+            .unwrap_or_else(|_| ProgressStyle::default_bar()),
+    );
+
+    if !plugin.supports_batch() {
+        // 插件未实现按文件处理：matches 本身不带文件信息，对每个文件重复调用 run
+        // 只会重复同一次执行，因此这里只整体运行一次，而不是按 paths 循环
+        anyhow::ensure!(
+            paths.len() <= 1,
+            "插件 '{}' 未实现批处理模式（run_one），不支持 --inputs/--jobs 指定多个文件",
+            plugin.name()
+        );
+        plugin.run(matches, ctx)?;
+        progress.inc(paths.len() as u64);
+        progress.finish();
+        return Ok(());
+    }
+
+    let pool = rayon::ThreadPoolBuilder::new().num_threads(jobs.max(1)).build()?;
+    let results: Vec<anyhow::Result<()>> = pool.install(|| {
+        paths
+            .par_iter()
+            .map(|path| {
+                let result = plugin.run_one(path, ctx);
+                progress.inc(1);
+                result
+            })
+            .collect()
+    });
+    progress.finish();
+
+    for result in results {
+        result?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::OutputFormat;
+    use clap::Command;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    // 不实现 run_one/supports_batch 的插件，用于验证批处理回退路径只整体执行一次
+    struct CountingPlugin {
+        runs: AtomicUsize,
+    }
+
+    impl Plugin for CountingPlugin {
+        fn name(&self) -> &'static str {
+            "counting"
+        }
+
+        fn command(&self) -> Command {
+            Command::new("counting")
+        }
+
+        fn run(&self, _matches: &ArgMatches, _ctx: &RunContext) -> anyhow::Result<()> {
+            self.runs.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn fallback_path_runs_plugin_exactly_once_for_single_input() {
+        let concrete = Arc::new(CountingPlugin {
+            runs: AtomicUsize::new(0),
+        });
+        let plugin: Arc<dyn Plugin> = concrete.clone();
+        let matches = Command::new("counting").get_matches_from(Vec::<String>::new());
+        let ctx = RunContext::new(OutputFormat::Table);
+
+        run_batch(&plugin, &matches, &ctx, vec![PathBuf::from("a.fa")], 1).unwrap();
+
+        assert_eq!(concrete.runs.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn fallback_path_rejects_multiple_inputs() {
+        let concrete = Arc::new(CountingPlugin {
+            runs: AtomicUsize::new(0),
+        });
+        let plugin: Arc<dyn Plugin> = concrete.clone();
+        let matches = Command::new("counting").get_matches_from(Vec::<String>::new());
+        let ctx = RunContext::new(OutputFormat::Table);
+
+        let result = run_batch(
+            &plugin,
+            &matches,
+            &ctx,
+            vec![PathBuf::from("a.fa"), PathBuf::from("b.fa")],
+            1,
+        );
+
+        assert!(result.is_err());
+        assert_eq!(concrete.runs.load(Ordering::SeqCst), 0);
+    }
+}
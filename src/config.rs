@@ -0,0 +1,109 @@
+// 加载 seqtc.toml 配置文件，为各插件提供默认参数值
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const CONFIG_FILE_NAME: &str = "seqtc.toml";
+
+/// 某个插件在配置文件中对应表里的默认参数（参数名 -> 默认值）
+pub type PluginDefaults = HashMap<String, String>;
+
+/// `seqtc.toml` 解析结果：每个 `[<plugin-name>]` 表对应一个插件的默认参数
+#[derive(Debug, Default)]
+pub struct SeqtcConfig {
+    plugins: HashMap<String, PluginDefaults>,
+}
+
+impl SeqtcConfig {
+    // 依次在当前目录和 `$HOME/.config/seqtc/` 下查找 seqtc.toml；
+    // 若指定了 override_path（来自 --config），则只使用该路径
+    pub fn load(override_path: Option<&Path>) -> Self {
+        let path = match override_path {
+            Some(path) => Some(path.to_path_buf()),
+            None => Self::discover(),
+        };
+
+        let Some(path) = path else {
+            return Self::default();
+        };
+
+        match fs::read_to_string(&path) {
+            Ok(content) => match toml::from_str::<toml::Value>(&content) {
+                Ok(value) => Self::from_toml(value),
+                Err(err) => {
+                    eprintln!("解析配置文件 {} 失败: {}", path.display(), err);
+                    Self::default()
+                }
+            },
+            Err(err) => {
+                eprintln!("读取配置文件 {} 失败: {}", path.display(), err);
+                Self::default()
+            }
+        }
+    }
+
+    fn discover() -> Option<PathBuf> {
+        let cwd_config = PathBuf::from(CONFIG_FILE_NAME);
+        if cwd_config.is_file() {
+            return Some(cwd_config);
+        }
+
+        let home = env::var("HOME").ok()?;
+        let home_config = PathBuf::from(home).join(".config").join("seqtc").join(CONFIG_FILE_NAME);
+        home_config.is_file().then_some(home_config)
+    }
+
+    fn from_toml(value: toml::Value) -> Self {
+        let mut plugins = HashMap::new();
+
+        if let toml::Value::Table(table) = value {
+            for (plugin_name, plugin_table) in table {
+                if let toml::Value::Table(args) = plugin_table {
+                    let defaults: PluginDefaults = args
+                        .into_iter()
+                        .filter_map(|(arg_name, arg_value)| {
+                            arg_value.as_str().map(|s| (arg_name, s.to_string()))
+                        })
+                        .collect();
+                    plugins.insert(plugin_name, defaults);
+                }
+            }
+        }
+
+        SeqtcConfig { plugins }
+    }
+
+    /// 返回指定插件在配置文件中提供的默认参数，未配置时返回 `None`
+    pub fn defaults_for(&self, plugin_name: &str) -> Option<&PluginDefaults> {
+        self.plugins.get(plugin_name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn load_reads_defaults_from_override_path() {
+        let dir = env::temp_dir().join(format!("seqtc_config_test_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(CONFIG_FILE_NAME);
+        fs::write(&path, "[sequence]\nmin = \"6\"\n").unwrap();
+
+        let config = SeqtcConfig::load(Some(&path));
+
+        assert_eq!(
+            config.defaults_for("sequence").unwrap().get("min"),
+            Some(&"6".to_string())
+        );
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn load_falls_back_to_default_when_file_missing() {
+        let config = SeqtcConfig::load(Some(Path::new("/no/such/seqtc.toml")));
+        assert!(config.defaults_for("sequence").is_none());
+    }
+}
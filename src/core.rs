@@ -1,14 +1,53 @@
 // 使用clap实现插件化的核心
+use crate::batch;
+use crate::config::SeqtcConfig;
+use crate::output::{OutputFormat, RunContext};
+use async_trait::async_trait;
 use clap::{ArgMatches, Command};
+use clap_complete::Shell;
+use libloading::Library;
+use std::path::Path;
+use std::sync::Arc;
+use tokio::runtime::Runtime;
 
-pub trait Plugin {
+#[async_trait]
+pub trait Plugin: Send + Sync {
     fn name(&self) -> &'static str;
     fn command(&self) -> Command; // 插件定义自己的命令
-    fn run(&self, matches: &ArgMatches); // 插件执行逻辑
+    fn run(&self, matches: &ArgMatches, ctx: &RunContext) -> anyhow::Result<()>; // 插件执行逻辑
+
+    // 插件加载后的异步初始化钩子（如打开索引文件、映射参考基因组），默认不做任何事
+    async fn on_load(&self) {}
+
+    // 插件卸载前的异步清理钩子，默认不做任何事
+    async fn on_unload(&self) {}
+
+    // 是否实现了按文件处理的 run_one；决定批处理模式走并行路径还是退化为顺序执行
+    fn supports_batch(&self) -> bool {
+        false
+    }
+
+    // 并行批处理模式下对单个输入文件执行插件逻辑，仅在 supports_batch 返回 true 时会被调用
+    fn run_one(&self, _path: &Path, _ctx: &RunContext) -> anyhow::Result<()> {
+        anyhow::bail!("{} 插件未实现批处理模式", self.name())
+    }
+}
+
+/// 动态库插件的 ABI 版本号，`loader` 在加载插件前会校验双方版本一致
+pub const CORE_VERSION: u32 = 1;
+
+/// 供动态库插件在 `plugin_entry` 中注册自身使用的注册器
+pub trait PluginRegistrar {
+    // 仅由动态加载的插件库通过 FFI 调用，本 crate 内没有直接调用点，属于预期的 dead_code
+    #[allow(dead_code)]
+    fn register_plugin(&mut self, plugin: Box<dyn Plugin>);
 }
 
 pub struct Core {
-    plugins: Vec<Box<dyn Plugin>>,  // 插件列表
+    plugins: Vec<Arc<dyn Plugin>>,  // 插件列表
+    libraries: Vec<Library>, // 已加载的动态库句柄，需与程序同生命周期以保证插件 vtable 有效
+    runtime: Runtime, // 供插件运行异步生命周期钩子及自身异步 I/O 使用，避免每个插件各建一套运行时
+    config: SeqtcConfig, // 从 seqtc.toml 加载的各插件默认参数
 }
 
 /*
@@ -20,54 +59,246 @@ Core 结构体包含一个插件列表，每个插件都是一个实现了 Plugi
 */
 impl Core {
     pub fn new() -> Self {
-        Core { plugins: Vec::new() }
+        Core {
+            plugins: Vec::new(),
+            libraries: Vec::new(),
+            runtime: Runtime::new().expect("无法创建 tokio 运行时"),
+            config: SeqtcConfig::default(),
+        }
     }
+
+    // 加载 seqtc.toml（或 override_path 指定的配置文件），需在 build_cli 之前调用
+    // 才能让插件参数的默认值生效
+    pub fn load_config(&mut self, override_path: Option<&Path>) {
+        self.config = SeqtcConfig::load(override_path);
+    }
+
     pub fn list_plugins(&self) {
         for plugin in &self.plugins {
-            println!("Registered plugin: {}", plugin.name());
+            match self.config.defaults_for(plugin.name()) {
+                Some(defaults) if !defaults.is_empty() => {
+                    let mut keys: Vec<&str> = defaults.keys().map(String::as_str).collect();
+                    keys.sort_unstable();
+                    println!(
+                        "Registered plugin: {} (config defaults: {})",
+                        plugin.name(),
+                        keys.join(", ")
+                    );
+                }
+                _ => println!("Registered plugin: {}", plugin.name()),
+            }
         }
     }
 
     pub fn register_plugin(&mut self, plugin: Box<dyn Plugin>) {
-        self.plugins.push(plugin);
+        self.plugins.push(Arc::from(plugin));
+    }
+
+    // 保留动态库句柄，防止其在插件仍被使用时被卸载
+    pub(crate) fn retain_library(&mut self, library: Library) {
+        self.libraries.push(library);
     }
 
     // 接收main函数传入的参数，根据参数执行对应的插件
     pub fn build_cli(&self) -> Command {
         let mut cli = Command::new("bio")
             .about("A bioinformatics framework with plugin support")
+            .arg(
+                clap::arg!(--config <PATH> "指定配置文件路径，覆盖默认的 seqtc.toml 查找顺序")
+                    .required(false),
+            )
             .subcommand(
                 Command::new("list")
                     .about("列出所有已注册的插件")
+            )
+            .subcommand(
+                Command::new("completions")
+                    .about("生成 shell 自动补全脚本")
+                    .arg(clap::arg!(<SHELL> "目标 shell").value_parser(clap::value_parser!(Shell)))
             );
-        
-        // 将每个插件的命令添加为子命令
+
+        // 将每个插件的命令添加为子命令：注入全局 --output、-j/--jobs、--inputs 标志，
+        // 并用 seqtc.toml 中对应的表填充缺省参数值
+        // 注意：不为 --output 设置短选项 -o，因为它会与部分插件自身的 -o 参数
+        // （如 tree 插件的 -o/--out）冲突，导致 clap 在构建该子命令时 panic
         for plugin in &self.plugins {
-            cli = cli.subcommand(plugin.command());
+            let mut sub_cmd = plugin
+                .command()
+                .arg(
+                    clap::arg!(--output <FORMAT> "输出格式 (table/json/tsv/csv)")
+                        .value_parser(clap::value_parser!(OutputFormat))
+                        .default_value("table"),
+                )
+                .arg(
+                    clap::arg!(-j --jobs <N> "批处理模式下的并发文件数")
+                        .value_parser(clap::value_parser!(usize))
+                        .default_value("1"),
+                )
+                .arg(
+                    clap::arg!(--inputs <PATH> ... "批量输入的文件路径或 glob 模式，指定后启用批处理模式")
+                        .required(false),
+                );
+            if let Some(defaults) = self.config.defaults_for(plugin.name()) {
+                // seqtc.toml 是用户编写的输入，其中的键可能拼错或指向不存在的参数；
+                // mut_arg 对未知 arg_id 会 panic，这里先校验一遍，跳过无效键并警告，而不是让整个 CLI 崩溃
+                let known_args: std::collections::HashSet<String> = sub_cmd
+                    .get_arguments()
+                    .map(|arg| arg.get_id().as_str().to_string())
+                    .collect();
+                for (arg_id, value) in defaults {
+                    if !known_args.contains(arg_id.as_str()) {
+                        eprintln!(
+                            "警告: seqtc.toml 中插件 '{}' 的配置项 '{}' 不是有效参数，已忽略",
+                            plugin.name(),
+                            arg_id
+                        );
+                        continue;
+                    }
+                    let owned_value = clap::builder::Str::from(value.clone());
+                    sub_cmd = sub_cmd.mut_arg(arg_id, |arg| arg.default_value(owned_value));
+                }
+            }
+            cli = cli.subcommand(sub_cmd);
         }
 
         cli
     }
 
-    pub fn run(&self, matches: &ArgMatches) {
+    pub fn run(&self, matches: &ArgMatches) -> anyhow::Result<()> {
+        self.runtime.block_on(self.run_async(matches))
+    }
+
+    // 异步运行路径：在插件 run 前后分别等待 on_load / on_unload 完成，
+    // 并将插件的执行结果传递给调用方以便设置正确的退出码
+    async fn run_async(&self, matches: &ArgMatches) -> anyhow::Result<()> {
         if let Some((command, sub_matches)) = matches.subcommand() {
 
             if command == "list" {
                 self.list_plugins();
-                return;
+                return Ok(());
             }
 
-
+            if command == "completions" {
+                let shell = *sub_matches.get_one::<Shell>("SHELL").expect("SHELL is required");
+                let mut cmd = self.build_cli();
+                let name = cmd.get_name().to_string();
+                clap_complete::generate(shell, &mut cmd, name, &mut std::io::stdout());
+                return Ok(());
+            }
 
             for plugin in &self.plugins {
                 if plugin.name() == command {
-                    plugin.run(sub_matches);
-                    return;
+                    plugin.on_load().await;
+
+                    let format = *sub_matches
+                        .get_one::<OutputFormat>("output")
+                        .unwrap_or(&OutputFormat::Table);
+
+                    let inputs: Vec<String> = sub_matches
+                        .get_many::<String>("inputs")
+                        .map(|values| values.cloned().collect())
+                        .unwrap_or_default();
+
+                    let task_plugin = Arc::clone(plugin);
+                    let task_matches = sub_matches.clone();
+
+                    let dispatch_task = if inputs.is_empty() {
+                        tokio::task::spawn_blocking(move || {
+                            let ctx = RunContext::new(format);
+                            task_plugin.run(&task_matches, &ctx)
+                        })
+                    } else {
+                        let jobs = *sub_matches.get_one::<usize>("jobs").unwrap_or(&1);
+                        tokio::task::spawn_blocking(move || {
+                            let ctx = RunContext::new(format);
+                            let paths = batch::expand_inputs(&inputs)?;
+                            batch::run_batch(&task_plugin, &task_matches, &ctx, paths, jobs)
+                        })
+                    };
+
+                    let result = tokio::select! {
+                        joined = dispatch_task => joined.map_err(anyhow::Error::from).and_then(|r| r),
+                        _ = tokio::signal::ctrl_c() => {
+                            println!("\n收到 Ctrl-C，正在关闭插件...");
+                            Ok(())
+                        }
+                    };
+
+                    self.shutdown_plugins().await;
+                    return result;
                 }
             }
         }
-        println!("Unknown command: {}", matches.subcommand_name().unwrap_or("none"));
+
+        // 未识别的子命令名由 clap 自身在 get_matches() 阶段拦截并给出 "did you mean" 提示，
+        // 此处只会在完全没有提供子命令时到达（例如单独运行 `bio`）
+        println!("未指定子命令，运行 `bio list` 查看所有已注册的插件，或加 --help 查看用法");
+        Ok(())
+    }
+
+    // 关闭时依次等待所有已加载插件的 on_unload 钩子完成
+    async fn shutdown_plugins(&self) {
+        for plugin in &self.plugins {
+            plugin.on_unload().await;
+        }
+    }
+}
+
+impl PluginRegistrar for Core {
+    fn register_plugin(&mut self, plugin: Box<dyn Plugin>) {
+        Core::register_plugin(self, plugin);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plugins;
+
+    // 未识别的子命令由 clap 自身在解析阶段拦截并给出提示，不需要也不会到达 Core::run_async 里的
+    // 兜底分支；这里直接驱动真实的"输错子命令名"路径，确认 clap 已经处理了这个场景
+    #[test]
+    fn build_cli_rejects_unrecognized_subcommand_via_clap() {
+        let mut core = Core::new();
+        plugins::register_plugins(&mut core);
+
+        let err = core
+            .build_cli()
+            .try_get_matches_from(vec!["bio", "seqence", "ACGT"])
+            .unwrap_err();
+
+        assert_eq!(err.kind(), clap::error::ErrorKind::InvalidSubcommand);
+    }
+
+    // 每个插件子命令都要能在不 panic 的情况下被 clap 校验，
+    // 防止全局注入的 -j/--jobs、--inputs、--output 等标志与插件自身参数的短选项发生冲突
+    #[test]
+    fn build_cli_accepts_every_plugin_subcommand() {
+        let mut core = Core::new();
+        plugins::register_plugins(&mut core);
+
+        for plugin in &core.plugins {
+            let name = plugin.name();
+            // 子命令本身的必填参数未必满足，这里只关心 clap 能否完成命令构建与参数解析
+            // 而不会因为重复的短选项（如 -o）而触发 debug_assert panic
+            let _ = core.build_cli().try_get_matches_from(vec!["bio", name]);
+        }
     }
 
+    // seqtc.toml 是用户编写的输入，其中出现未知的参数名不应让整个 CLI panic
+    #[test]
+    fn build_cli_ignores_unknown_config_keys_instead_of_panicking() {
+        let dir = std::env::temp_dir().join(format!("seqtc_core_test_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let config_path = dir.join("seqtc.toml");
+        std::fs::write(&config_path, "[sequence]\ntypo_does_not_exist = \"foo\"\n").unwrap();
 
+        let mut core = Core::new();
+        plugins::register_plugins(&mut core);
+        core.load_config(Some(&config_path));
+
+        let _ = core.build_cli();
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
 }
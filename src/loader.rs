@@ -0,0 +1,99 @@
+// 动态加载插件：从 plugins/ 目录及 SEQTC_PLUGIN_PATH 环境变量扫描共享库
+use crate::core::{Core, PluginRegistrar, CORE_VERSION};
+use libloading::{Library, Symbol};
+use std::env;
+use std::path::{Path, PathBuf};
+
+/// 插件动态库导出的入口函数签名
+/// 注意：这不是真正的 C ABI 边界，而是同一份 Rust 定义在核心与插件动态库之间共享的约定
+/// （两者必须用同一版本的 core crate 编译），`&mut dyn PluginRegistrar` 的胖指针布局
+/// 因此是稳定的；`extern "C"` 只固定调用约定（符号名不被修饰靠插件侧的 `#[no_mangle]`），
+/// 而非声明真正的 C 类型
+#[allow(improper_ctypes_definitions)]
+type PluginEntryFn = unsafe extern "C" fn(registrar: &mut dyn PluginRegistrar);
+
+const CORE_VERSION_SYMBOL: &[u8] = b"PLUGIN_CORE_VERSION\0";
+const ENTRY_SYMBOL: &[u8] = b"plugin_entry\0";
+
+/// 扫描默认的 `plugins/` 目录以及 `SEQTC_PLUGIN_PATH` 指定的目录，
+/// 加载其中所有的 `.so`/`.dll`/`.dylib` 插件并注册到 `core`
+pub fn load_plugins(core: &mut Core) {
+    let mut dirs = vec![PathBuf::from("plugins")];
+    if let Ok(path) = env::var("SEQTC_PLUGIN_PATH") {
+        dirs.extend(env::split_paths(&path));
+    }
+
+    for dir in dirs {
+        if dir.is_dir() {
+            load_plugins_from_dir(core, &dir);
+        }
+    }
+}
+
+fn load_plugins_from_dir(core: &mut Core, dir: &Path) {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(err) => {
+            eprintln!("无法读取插件目录 {}: {}", dir.display(), err);
+            return;
+        }
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if is_dynamic_library(&path) {
+            if let Err(err) = load_plugin_file(core, &path) {
+                eprintln!("加载插件 {} 失败: {}", path.display(), err);
+            }
+        }
+    }
+}
+
+fn is_dynamic_library(path: &Path) -> bool {
+    matches!(
+        path.extension().and_then(|ext| ext.to_str()),
+        Some("so") | Some("dll") | Some("dylib")
+    )
+}
+
+// 加载单个插件动态库：校验 ABI 版本后调用其 plugin_entry 完成注册
+fn load_plugin_file(core: &mut Core, path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    // SAFETY: 插件动态库来自受信任的 plugins/ 目录，加载第三方插件时需自行保证其可信
+    let library = unsafe { Library::new(path)? };
+
+    let plugin_version: u32 = unsafe {
+        let symbol: Symbol<*const u32> = library.get(CORE_VERSION_SYMBOL)?;
+        **symbol
+    };
+    if plugin_version != CORE_VERSION {
+        return Err(format!(
+            "插件 ABI 版本不匹配: 插件为 {}，核心为 {}",
+            plugin_version, CORE_VERSION
+        )
+        .into());
+    }
+
+    unsafe {
+        let entry: Symbol<PluginEntryFn> = library.get(ENTRY_SYMBOL)?;
+        entry(core);
+    }
+
+    // 保留 Library 句柄，确保插件的 vtable 在程序运行期间始终有效
+    core.retain_library(library);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn is_dynamic_library_matches_known_extensions_only() {
+        assert!(is_dynamic_library(Path::new("plugin.so")));
+        assert!(is_dynamic_library(Path::new("plugin.dll")));
+        assert!(is_dynamic_library(Path::new("plugin.dylib")));
+        assert!(!is_dynamic_library(Path::new("plugin.txt")));
+        assert!(!is_dynamic_library(Path::new("plugin")));
+    }
+}
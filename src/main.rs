@@ -1,18 +1,49 @@
+mod batch;
+mod config;
 mod core;
+mod loader;
+mod output;
 mod plugins;
 
 // use clap::Parser;
 use core::Core;
+use std::path::PathBuf;
 
 fn main() {
     let mut core = Core::new();
-    
+
     // 注册插件
     plugins::register_plugins(&mut core);
 
+    // 从 plugins/ 目录及 SEQTC_PLUGIN_PATH 加载第三方动态库插件
+    loader::load_plugins(&mut core);
+
+    // --config 需要在 build_cli 构建插件参数默认值之前解析出来
+    core.load_config(find_config_flag().as_deref());
+
     // 解析命令行输入
     let cli = core.build_cli().get_matches();
 
-    // 根据解析结果运行插件
-    core.run(&cli);
+    // 根据解析结果运行插件，出错时以非零状态码退出
+    if let Err(err) = core.run(&cli) {
+        eprintln!("Error: {:#}", err);
+        std::process::exit(1);
+    }
+}
+
+// 在完整的 clap 解析之前，先扫描出 --config，用于确定 seqtc.toml 的来源
+// 需要同时支持 `--config <path>` 和 `--config=<path>` 两种写法，与 clap 自身的解析行为保持一致
+fn find_config_flag() -> Option<PathBuf> {
+    let args: Vec<String> = std::env::args().collect();
+
+    for (index, arg) in args.iter().enumerate() {
+        if let Some(value) = arg.strip_prefix("--config=") {
+            return Some(PathBuf::from(value));
+        }
+        if arg == "--config" {
+            return args.get(index + 1).map(PathBuf::from);
+        }
+    }
+
+    None
 }
\ No newline at end of file
@@ -0,0 +1,122 @@
+// 统一的结构化输出子系统，供所有插件复用，避免每个插件各自实现一套格式化逻辑
+use std::io::IsTerminal;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+#[value(rename_all = "lowercase")]
+pub enum OutputFormat {
+    Table,
+    Json,
+    Tsv,
+    Csv,
+}
+
+/// 每次插件运行时下发的运行期上下文，携带输出格式以及由格式和 TTY 检测得出的是否着色
+pub struct RunContext {
+    format: OutputFormat,
+    color: bool,
+}
+
+impl RunContext {
+    pub fn new(format: OutputFormat) -> Self {
+        // 非 TTY（比如被管道到其他程序）时自动关闭颜色和表格修饰，保持纯文本
+        let color = format == OutputFormat::Table && std::io::stdout().is_terminal();
+        RunContext { format, color }
+    }
+
+    /// 按当前输出格式渲染一组带表头的行，这是插件产出表格结果的统一入口
+    pub fn emit_rows(&self, headers: &[&str], rows: &[Vec<String>]) {
+        match self.format {
+            OutputFormat::Table => self.emit_table(headers, rows),
+            OutputFormat::Json => self.emit_json(headers, rows),
+            OutputFormat::Tsv => self.emit_delimited(headers, rows, '\t'),
+            OutputFormat::Csv => self.emit_csv(headers, rows),
+        }
+    }
+
+    fn emit_table(&self, headers: &[&str], rows: &[Vec<String>]) {
+        let mut widths: Vec<usize> = headers.iter().map(|h| h.len()).collect();
+        for row in rows {
+            for (i, cell) in row.iter().enumerate() {
+                if let Some(width) = widths.get_mut(i) {
+                    *width = (*width).max(cell.len());
+                }
+            }
+        }
+
+        let header_cells: Vec<String> = headers.iter().map(|h| h.to_string()).collect();
+        self.print_table_row(&header_cells, &widths, true);
+        for row in rows {
+            self.print_table_row(row, &widths, false);
+        }
+    }
+
+    fn print_table_row(&self, cells: &[String], widths: &[usize], is_header: bool) {
+        let line: Vec<String> = cells
+            .iter()
+            .enumerate()
+            .map(|(i, cell)| {
+                let width = widths.get(i).copied().unwrap_or(cell.len());
+                format!("{:<width$}", cell, width = width)
+            })
+            .collect();
+        let line = line.join("  ");
+
+        if is_header && self.color {
+            println!("\x1b[1m{}\x1b[0m", line.trim_end());
+        } else {
+            println!("{}", line.trim_end());
+        }
+    }
+
+    fn emit_json(&self, headers: &[&str], rows: &[Vec<String>]) {
+        let objects: Vec<serde_json::Value> = rows
+            .iter()
+            .map(|row| {
+                let mut object = serde_json::Map::new();
+                for (key, value) in headers.iter().zip(row.iter()) {
+                    object.insert((*key).to_string(), serde_json::Value::String(value.clone()));
+                }
+                serde_json::Value::Object(object)
+            })
+            .collect();
+
+        println!("{}", serde_json::Value::Array(objects));
+    }
+
+    fn emit_delimited(&self, headers: &[&str], rows: &[Vec<String>], delimiter: char) {
+        println!("{}", headers.join(&delimiter.to_string()));
+        for row in rows {
+            println!("{}", row.join(&delimiter.to_string()));
+        }
+    }
+
+    fn emit_csv(&self, headers: &[&str], rows: &[Vec<String>]) {
+        let header_cells: Vec<String> = headers.iter().map(|h| csv_escape(h)).collect();
+        println!("{}", header_cells.join(","));
+        for row in rows {
+            let cells: Vec<String> = row.iter().map(|cell| csv_escape(cell)).collect();
+            println!("{}", cells.join(","));
+        }
+    }
+}
+
+// 按 CSV 惯例转义单元格：包含逗号/引号/换行时用引号包裹，并将内部引号翻倍
+fn csv_escape(cell: &str) -> String {
+    if cell.contains(',') || cell.contains('"') || cell.contains('\n') {
+        format!("\"{}\"", cell.replace('"', "\"\""))
+    } else {
+        cell.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn csv_escape_quotes_only_when_needed() {
+        assert_eq!(csv_escape("plain"), "plain");
+        assert_eq!(csv_escape("a,b"), "\"a,b\"");
+        assert_eq!(csv_escape("a\"b"), "\"a\"\"b\"");
+    }
+}
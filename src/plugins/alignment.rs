@@ -1,4 +1,5 @@
 use crate::core::Plugin;
+use crate::output::RunContext;
 use clap::{ArgMatches, Command};
 
 pub struct AlignmentPlugin;
@@ -15,18 +16,31 @@ impl Plugin for AlignmentPlugin {
             .arg(clap::arg!(<SEQ2> "第二个DNA序列"))
     }
 
-    fn run(&self, matches: &ArgMatches) {
+    fn run(&self, matches: &ArgMatches, ctx: &RunContext) -> anyhow::Result<()> {
         // 使用 get_one 获取参数值
         let seq1 = matches.get_one::<String>("SEQ1").expect("SEQ1 is required");
         let seq2 = matches.get_one::<String>("SEQ2").expect("SEQ2 is required");
-        
-        let (nw_score, aligned_a, aligned_b) = needleman_wunsch(seq1, seq2, 1, -1);
-        println!("Needleman-Wunsch Score: {}", nw_score);
-        println!("Aligned:\n{}\n{}", aligned_a, aligned_b);
-
-        let (sw_score, aligned_a, aligned_b) = smith_waterman(seq1, seq2, 1, -1);
-        println!("Smith-Waterman Score: {}", sw_score);
-        println!("Aligned:\n{}\n{}", aligned_a, aligned_b);
+
+        let (nw_score, nw_aligned_a, nw_aligned_b) = needleman_wunsch(seq1, seq2, 1, -1);
+        let (sw_score, sw_aligned_a, sw_aligned_b) = smith_waterman(seq1, seq2, 1, -1);
+
+        let rows = vec![
+            vec![
+                "Needleman-Wunsch".to_string(),
+                nw_score.to_string(),
+                nw_aligned_a,
+                nw_aligned_b,
+            ],
+            vec![
+                "Smith-Waterman".to_string(),
+                sw_score.to_string(),
+                sw_aligned_a,
+                sw_aligned_b,
+            ],
+        ];
+        ctx.emit_rows(&["Algorithm", "Score", "AlignedA", "AlignedB"], &rows);
+
+        Ok(())
     }
 }
 
@@ -1,4 +1,5 @@
 use crate::core::Plugin;
+use crate::output::RunContext;
 use clap::{ArgMatches, Command};
 // use std::collections::HashMap;
 pub struct SequencePlugin;
@@ -23,7 +24,7 @@ impl Plugin for SequencePlugin {
             )
             .group(
                 clap::ArgGroup::new("structure") // 参数组名
-                    .args(&["poly", "repeat"]) // 将 -p 和 -t 作为互斥参数
+                    .args(["poly", "repeat"]) // 将 -p 和 -t 作为互斥参数
                     .required(false), // 可选互斥参数
             )
             // 分别定义 -p 和 -t 参数
@@ -48,7 +49,7 @@ impl Plugin for SequencePlugin {
 
     }
 
-    fn run(&self, matches: &ArgMatches) {
+    fn run(&self, matches: &ArgMatches, ctx: &RunContext) -> anyhow::Result<()> {
         let sequence = matches.get_one::<String>("SEQUENCE").expect("SEQUENCE is required");
 
         // 如果指定了 -r 或 --reverse 参数，则计算反向互补序列
@@ -70,26 +71,24 @@ impl Plugin for SequencePlugin {
                 .parse()
                 .expect("Invalid minimum repeat number");
 
-            if let Some(base) = matches.get_one::<String>("base") {
+            let poly_result = if let Some(base) = matches.get_one::<String>("base") {
                 // 如果用户指定了要检测的碱基
-                let poly_result = detect_poly_structure(sequence, base.chars().next().unwrap(), min_repeats);
-                if poly_result.is_empty() {
-                    println!("No poly structure detected for base {} with minimum {} repeats.", base, min_repeats);
-                } else {
-                    for (base, repeats, start_pos) in poly_result {
-                        println!("Poly structure: Base {}, Repeats {}, Start Position {}", base, repeats, start_pos);
-                    }
-                }
+                detect_poly_structure(sequence, base.chars().next().unwrap(), min_repeats)
             } else {
                 // 没有指定碱基，检测所有可能的 poly 结构
-                let all_poly_results = detect_all_poly_structures(sequence, min_repeats);
-                if all_poly_results.is_empty() {
-                    println!("No poly structures detected with minimum {} repeats.", min_repeats);
-                } else {
-                    for (base, repeats, start_pos) in all_poly_results {
-                        println!("Poly structure: Base {}, Repeats {}, Start Position {}", base, repeats, start_pos);
-                    }
-                }
+                detect_all_poly_structures(sequence, min_repeats)
+            };
+
+            if poly_result.is_empty() {
+                println!("No poly structures detected with minimum {} repeats.", min_repeats);
+            } else {
+                let rows: Vec<Vec<String>> = poly_result
+                    .into_iter()
+                    .map(|(base, repeats, start_pos)| {
+                        vec![base.to_string(), repeats.to_string(), start_pos.to_string()]
+                    })
+                    .collect();
+                ctx.emit_rows(&["Base", "Repeats", "StartPosition"], &rows);
             }
         }
 
@@ -103,12 +102,13 @@ impl Plugin for SequencePlugin {
             if repeats.is_empty() {
                 println!("No repeating structures found.");
             } else {
-                for (base, count, start_pos) in repeats {
-                    println!(
-                        "Repeat structure detected: Base {}, repeats {} times, starts at position {}",
-                        base, count, start_pos
-                    );
-                }
+                let rows: Vec<Vec<String>> = repeats
+                    .into_iter()
+                    .map(|(base, count, start_pos)| {
+                        vec![base, count.to_string(), start_pos.to_string()]
+                    })
+                    .collect();
+                ctx.emit_rows(&["Repeat", "Count", "StartPosition"], &rows);
             }
         }
 
@@ -116,6 +116,8 @@ impl Plugin for SequencePlugin {
         if !matches.get_flag("reverse") && !matches.get_flag("gc") {
             println!("Please specify either --reverse (-r) or --gc (-g) to perform an operation.");
         }
+
+        Ok(())
     }
 }
 
@@ -1,4 +1,5 @@
 use crate::core::Plugin;
+use crate::output::RunContext;
 use clap::{ArgMatches, Command};
 use bio::io::fasta;
 use std::fs::{File, write};
@@ -32,16 +33,17 @@ impl Plugin for TreePlugin {
             .arg(clap::arg!(-o --out <NEWICK_FILE> "输出的Newick格式文件路径"))
     }
 
-    fn run(&self, matches: &ArgMatches) {
+    fn run(&self, matches: &ArgMatches, _ctx: &RunContext) -> anyhow::Result<()> {
         let model = matches.get_one::<String>("model").expect("请选择需要使用的模型");
         let fa_file = matches.get_one::<String>("fasta").expect("需要输入FASTA文件路径");
         let newick_file = matches.get_one::<String>("out").expect("需要输出Newick文件路径");
 
         // 读取序列并构建进化树
-        match self.build_tree(fa_file ,model, newick_file) {
-            Ok(_) => println!("进化树已成功保存到: {}", newick_file),
-            Err(err) => eprintln!("构建进化树时发生错误: {}", err),
-        }
+        self.build_tree(fa_file, model, newick_file)
+            .map_err(|err| anyhow::anyhow!("构建进化树时发生错误: {}", err))?;
+        println!("进化树已成功保存到: {}", newick_file);
+
+        Ok(())
     }
 }
 
@@ -111,6 +113,8 @@ fn compute_distance_matrix(sequences: &[String]) -> Vec<Vec<f64>> {
 }
 
 // 构建邻接法的进化树
+// 这里按下标同时遍历 dist/q_matrix 等多个并行矩阵，enumerate 无法简化，保留显式下标循环
+#[allow(clippy::needless_range_loop)]
 fn build_phylogenetic_tree(distance_matrix: &[Vec<f64>], names: &[String]) -> TreeNode {
     let mut n = distance_matrix.len();
     let mut dist = distance_matrix.to_vec();
@@ -204,14 +208,12 @@ fn build_phylogenetic_tree(distance_matrix: &[Vec<f64>], names: &[String]) -> Tr
     let root_name = format!("({},{})", node_names[0], node_names[1]);
     let node_left = tree_nodes.remove(&node_names[0]).unwrap();
     let node_right = tree_nodes.remove(&node_names[1]).unwrap();
-    let root = TreeNode {
-        name: root_name.clone(),
+    TreeNode {
+        name: root_name,
         left: Some(Box::new(node_left)),
         right: Some(Box::new(node_right)),
         distance: 0.0,
-    };
-
-    root
+    }
 }
 
 
@@ -318,15 +320,16 @@ fn swap_subtrees(mut tree: TreeNode) -> TreeNode {
 }
 
 // 计算节点的似然值
-fn calculate_likelihood(node: &TreeNode, sequences: &HashMap<String, String>, substitution_rate: f64) -> f64 {
+// sequences 目前只在递归调用间透传，暂未参与似然计算（待引入真实替换模型时使用）
+fn calculate_likelihood(node: &TreeNode, _sequences: &HashMap<String, String>, substitution_rate: f64) -> f64 {
     // 如果是叶节点，直接返回
     if node.left.is_none() && node.right.is_none() {
         return 1.0;
     }
 
     // 获取左右子节点的似然值
-    let left_likelihood = calculate_likelihood(node.left.as_ref().unwrap(), sequences, substitution_rate);
-    let right_likelihood = calculate_likelihood(node.right.as_ref().unwrap(), sequences, substitution_rate);
+    let left_likelihood = calculate_likelihood(node.left.as_ref().unwrap(), _sequences, substitution_rate);
+    let right_likelihood = calculate_likelihood(node.right.as_ref().unwrap(), _sequences, substitution_rate);
 
     // 根据替换模型计算父节点的似然值
     let distance = node.distance;